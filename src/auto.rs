@@ -0,0 +1,64 @@
+//! Autoref specialization between [`std::str::FromStr`] and [`custom::FromStr`].
+//!
+//! Backs the [`get!`](crate::get) macro. Everything here is `#[doc(hidden)]`: it only needs
+//! to be `pub` so the macro expansion (which runs in the caller's crate) can name it.
+
+use std::marker::PhantomData;
+
+use crate::custom;
+
+/// A tag type that only exists to host the [`ViaCustom`]/[`ViaStd`] impls below.
+#[doc(hidden)]
+pub struct Tag<T>(pub PhantomData<T>);
+
+/// Error produced when the value selected by [`get!`](crate::get) fails to parse.
+///
+/// This stringifies the underlying `T::Err` (whichever of [`custom::FromStr::Err`] or
+/// [`std::str::FromStr::Err`] was selected) instead of preserving it, because [`ViaCustom`]
+/// and [`ViaStd`] have different associated `Err` types and [`get!`](crate::get) needs one
+/// concrete error type to return regardless of which was picked. Unlike [`must`](crate::must)
+/// and [`maybe`](crate::maybe), callers can't `downcast`/`source()` their way back to e.g. the
+/// original `ParseIntError` - only its `Display` text survives.
+#[derive(Debug, thiserror::Error)]
+#[doc(hidden)]
+#[error("{0}")]
+pub struct GetError(pub String);
+
+/// Parses via [`custom::FromStr`].
+///
+/// Implemented for `&Tag<T>` (rather than `Tag<T>`) so that, when `T` implements
+/// [`custom::FromStr`], method resolution on `&&Tag::<T>(..)` finds this impl before it tries
+/// [`ViaStd`] below, which needs one more deref. This is the autoref-specialization trick
+/// `clap` uses for its `auto` value parser; it only works when `T` is a concrete type at the
+/// call site, which is why [`get!`](crate::get) is a macro rather than a generic function.
+#[doc(hidden)]
+pub trait ViaCustom<T> {
+    fn get_via(&self, s: &str) -> Result<T, GetError>;
+}
+
+impl<T> ViaCustom<T> for &Tag<T>
+where
+    T: custom::FromStr,
+    <T as custom::FromStr>::Err: std::fmt::Display,
+{
+    fn get_via(&self, s: &str) -> Result<T, GetError> {
+        <T as custom::FromStr>::from_str(s).map_err(|err| GetError(err.to_string()))
+    }
+}
+
+/// Parses via [`std::str::FromStr`]. The fallback used whenever `T` doesn't also implement
+/// [`custom::FromStr`].
+#[doc(hidden)]
+pub trait ViaStd<T> {
+    fn get_via(&self, s: &str) -> Result<T, GetError>;
+}
+
+impl<T> ViaStd<T> for Tag<T>
+where
+    T: std::str::FromStr,
+    <T as std::str::FromStr>::Err: std::fmt::Display,
+{
+    fn get_via(&self, s: &str) -> Result<T, GetError> {
+        <T as std::str::FromStr>::from_str(s).map_err(|err| GetError(err.to_string()))
+    }
+}
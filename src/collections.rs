@@ -0,0 +1,114 @@
+//! Parsing of delimiter-separated collections.
+//!
+//! See [`List`].
+
+use std::str::FromStr;
+
+/// A list of `T` parsed from a single delimited string value.
+///
+/// The raw value is split on `SEP`, each fragment is trimmed and parsed via
+/// `T::from_str`. A single trailing empty fragment is skipped, so `"a,b,"`
+/// yields two items rather than an error or a spurious empty one.
+///
+/// # Example
+///
+/// ```no_run
+/// use envfury::List;
+///
+/// let List::<u16, ';'>(ports) = envfury::must("PORTS")?;
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+#[derive(Debug)]
+pub struct List<T, const SEP: char = ','>(pub Vec<T>);
+
+/// Error parsing a [`List`].
+#[derive(Debug, thiserror::Error)]
+#[error("unable to parse fragment {index} ({text:?}): {source}")]
+pub struct ListError<T> {
+    /// The index of the fragment that failed to parse.
+    pub index: usize,
+    /// The text of the fragment that failed to parse.
+    pub text: String,
+    /// The underlying parse error.
+    #[source]
+    pub source: T,
+}
+
+impl<T, const SEP: char> FromStr for List<T, SEP>
+where
+    T: FromStr,
+{
+    type Err = ListError<T::Err>;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut fragments: Vec<&str> = s.split(SEP).collect();
+        if fragments
+            .last()
+            .is_some_and(|fragment| fragment.trim().is_empty())
+        {
+            fragments.pop();
+        }
+        let items = fragments
+            .into_iter()
+            .enumerate()
+            .map(|(index, fragment)| {
+                let text = fragment.trim();
+                text.parse().map_err(|source| ListError {
+                    index,
+                    text: text.to_owned(),
+                    source,
+                })
+            })
+            .collect::<Result<Vec<T>, _>>()?;
+        Ok(List(items))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_items() {
+        let List::<u16>(items) = "80,443".parse().unwrap();
+        assert_eq!(items, vec![80, 443]);
+    }
+
+    #[test]
+    fn trims_whitespace_around_fragments() {
+        let List::<u16>(items) = " 80 , 443 ".parse().unwrap();
+        assert_eq!(items, vec![80, 443]);
+    }
+
+    #[test]
+    fn skips_a_single_trailing_empty_fragment() {
+        let List::<u16>(items) = "80,443,".parse().unwrap();
+        assert_eq!(items, vec![80, 443]);
+    }
+
+    #[test]
+    fn does_not_skip_an_empty_fragment_in_the_middle() {
+        let err = "80,,443".parse::<List<u16>>().unwrap_err();
+        assert_eq!(err.index, 1);
+        assert_eq!(err.text, "");
+    }
+
+    #[test]
+    fn uses_the_custom_separator() {
+        let List::<u16, ';'>(items) = "80;443".parse().unwrap();
+        assert_eq!(items, vec![80, 443]);
+    }
+
+    #[test]
+    fn names_the_failing_fragment() {
+        let err = "80,oops,443".parse::<List<u16>>().unwrap_err();
+        assert_eq!(err.index, 1);
+        assert_eq!(err.text, "oops");
+    }
+
+    #[test]
+    fn empty_input_yields_an_empty_list() {
+        let List::<u16>(items) = "".parse().unwrap();
+        assert!(items.is_empty());
+    }
+}
@@ -1,29 +1,33 @@
 //! Custom parsing.
 //!
-//! See [`Custom`].
+//! See [`Custom`] and [`FromOsStr`].
 
 /// Use [`Custom`] to override the standard parsing implementation for your type.
 ///
 /// # Example
 ///
-/// ```no_run
+/// ```
 /// use envfury::custom::FromStr;
 ///
-/// impl envfury::custom::FromStr for u8 {
+/// struct OneOrTwo(u8);
+///
+/// impl FromStr for OneOrTwo {
 ///     type Err = &'static str;
 ///
 ///     fn from_str(s: &str) -> Result<Self, Self::Err> {
-///         if (s == "one") {
-///             Ok(1)
-///         } else if (s == "two") {
-///             Ok(2)
+///         if s == "one" {
+///             Ok(OneOrTwo(1))
+///         } else if s == "two" {
+///             Ok(OneOrTwo(2))
 ///         } else {
 ///             Err(r#"not "one" or "two""#)
 ///         }
 ///     }
 /// }
 ///
-/// let envfury::Custom::<u8>(myvar) = envfury::must("MY_ONE_OR_TWO");
+/// std::env::set_var("MY_ONE_OR_TWO", "two");
+/// let envfury::Custom::<OneOrTwo>(myvar) = envfury::must("MY_ONE_OR_TWO").unwrap();
+/// assert_eq!(myvar.0, 2);
 /// ```
 pub struct Custom<T>(pub T);
 
@@ -51,3 +55,77 @@ where
         Ok(Custom(val))
     }
 }
+
+/// A custom conversion from [`OsStr`](std::ffi::OsStr), to enable parsing values that may not
+/// be valid unicode.
+///
+/// Implement this trait for any type that you want to construct directly from the raw value
+/// of an env var, without going through [`std::env::var`] (and its `NonUnicode` failure mode).
+/// See [`crate::maybe_os`], [`crate::must_os`] and [`crate::or_os`] for how this is used.
+pub trait FromOsStr: Sized {
+    /// The reason the conversion may fail.
+    type Err;
+
+    /// Convert the given `OsStr` into `Self`.
+    fn from_os_str(s: &std::ffi::OsStr) -> Result<Self, Self::Err>;
+}
+
+/// Error converting a value via the [`FromOsStr`] impl for [`ViaStr`], which parses through
+/// [`std::str::FromStr`].
+#[derive(Debug, thiserror::Error)]
+pub enum FromOsStrError<T> {
+    /// The value was not a valid unicode.
+    #[error("value is not a valid unicode")]
+    NonUnicode,
+    /// The value could not be parsed from a string.
+    #[error(transparent)]
+    Parse(T),
+}
+
+impl FromOsStr for std::path::PathBuf {
+    type Err = std::convert::Infallible;
+
+    fn from_os_str(s: &std::ffi::OsStr) -> Result<Self, Self::Err> {
+        Ok(Self::from(s))
+    }
+}
+
+impl FromOsStr for std::ffi::OsString {
+    type Err = std::convert::Infallible;
+
+    fn from_os_str(s: &std::ffi::OsStr) -> Result<Self, Self::Err> {
+        Ok(s.to_os_string())
+    }
+}
+
+/// Use [`ViaStr`] to parse `T` via [`std::str::FromStr`] through a [`FromOsStr`]-based entry
+/// point, e.g. [`crate::maybe_os`], [`crate::must_os`] or [`crate::or_os`].
+///
+/// This can't be a blanket `impl<T: std::str::FromStr> FromOsStr for T`, since
+/// [`PathBuf`](std::path::PathBuf) and [`OsString`](std::ffi::OsString) already implement both
+/// traits and that would conflict with their impls above; wrapping in [`ViaStr`] opts in
+/// explicitly instead.
+///
+/// # Example
+///
+/// ```
+/// use envfury::custom::ViaStr;
+///
+/// std::env::set_var("PORT", "8080");
+/// let ViaStr::<u16>(port) = envfury::must_os("PORT")?;
+/// assert_eq!(port, 8080);
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+pub struct ViaStr<T>(pub T);
+
+impl<T> FromOsStr for ViaStr<T>
+where
+    T: std::str::FromStr,
+{
+    type Err = FromOsStrError<T::Err>;
+
+    fn from_os_str(s: &std::ffi::OsStr) -> Result<Self, Self::Err> {
+        let s = s.to_str().ok_or(FromOsStrError::NonUnicode)?;
+        T::from_str(s).map(Self).map_err(FromOsStrError::Parse)
+    }
+}
@@ -0,0 +1,208 @@
+//! Variable interpolation across multiple env vars.
+//!
+//! See [`interpolate`].
+
+use std::{collections::HashSet, env::VarError, str::FromStr};
+
+use crate::{Error, InputString, ValueError};
+
+/// Error expanding `${OTHER_VAR}` references in a value.
+#[derive(Debug, thiserror::Error)]
+pub enum ExpandError {
+    /// A referenced variable is not set.
+    #[error("referenced variable {0} is not set")]
+    NotSet(String),
+    /// A referenced variable is not a valid unicode.
+    #[error("referenced variable {0} is not a valid unicode")]
+    NonUnicode(String),
+    /// A `${` reference was not closed with a matching `}`.
+    #[error("unterminated reference starting at byte {0}")]
+    Unterminated(usize),
+    /// Expanding a reference would recurse into itself.
+    #[error("cyclic reference to {0}")]
+    Cycle(String),
+}
+
+/// Error while processing an interpolated variable.
+#[derive(Debug, thiserror::Error)]
+pub enum InterpolateError<T> {
+    /// The variable was not set.
+    #[error("not set")]
+    NotSet,
+    /// Expanding `${...}` references in the value failed.
+    #[error(transparent)]
+    Expand(ExpandError),
+    /// The expanded value could not be processed.
+    #[error(transparent)]
+    Value(ValueError<T>),
+}
+
+/// Get the value of environment variable `key`, expand any `${OTHER_VAR}` references found
+/// within it, and parse the result into `T`.
+///
+/// For example, given `ADDR=127.0.0.1`, `PORT=8000` and `HOST=${ADDR}:${PORT}`, calling
+/// `interpolate::<SocketAddr>("HOST")` substitutes both references before parsing.
+///
+/// A literal `$` can be produced with `$$` or `\$`. Returns an error if the variable is not
+/// set, if expanding a reference fails (missing variable, invalid unicode, unterminated
+/// reference, or a reference cycle), or if the expanded value could not be parsed.
+pub fn interpolate<T>(key: &'static str) -> Result<T, Error<InterpolateError<T::Err>>>
+where
+    T: FromStr,
+    T::Err: std::fmt::Display,
+{
+    let val = match std::env::var(key) {
+        Ok(val) => val,
+        Err(VarError::NotPresent) => return Err(Error::new(key, InterpolateError::NotSet)),
+        Err(VarError::NotUnicode(_)) => {
+            return Err(Error::new(
+                key,
+                InterpolateError::Value(ValueError::NonUnicode),
+            ))
+        }
+    };
+
+    let mut visited = HashSet::new();
+    visited.insert(key.to_owned());
+    let expanded =
+        expand(&val, &mut visited).map_err(|err| Error::new(key, InterpolateError::Expand(err)))?;
+
+    match expanded.parse() {
+        Ok(val) => Ok(val),
+        Err(source) => Err(Error::new(
+            key,
+            InterpolateError::Value(ValueError::Parse {
+                input: InputString(expanded),
+                source,
+            }),
+        )),
+    }
+}
+
+/// Expand `${OTHER_VAR}` references found in `template`, recursing into referenced values
+/// while tracking `visited` keys to detect cycles.
+fn expand(template: &str, visited: &mut HashSet<String>) -> Result<String, ExpandError> {
+    let mut out = String::with_capacity(template.len());
+    let mut chars = template.char_indices().peekable();
+
+    while let Some((i, c)) = chars.next() {
+        if c == '\\' && chars.peek().map(|(_, c)| *c) == Some('$') {
+            chars.next();
+            out.push('$');
+            continue;
+        }
+
+        if c != '$' {
+            out.push(c);
+            continue;
+        }
+
+        match chars.peek().copied() {
+            Some((_, '$')) => {
+                chars.next();
+                out.push('$');
+            }
+            Some((_, '{')) => {
+                chars.next();
+                let mut name = String::new();
+                let mut closed = false;
+                for (_, ch) in chars.by_ref() {
+                    if ch == '}' {
+                        closed = true;
+                        break;
+                    }
+                    name.push(ch);
+                }
+                if !closed {
+                    return Err(ExpandError::Unterminated(i));
+                }
+
+                if !visited.insert(name.clone()) {
+                    return Err(ExpandError::Cycle(name));
+                }
+                let value = match std::env::var(&name) {
+                    Ok(value) => value,
+                    Err(VarError::NotPresent) => return Err(ExpandError::NotSet(name)),
+                    Err(VarError::NotUnicode(_)) => return Err(ExpandError::NonUnicode(name)),
+                };
+                let expanded = expand(&value, visited)?;
+                visited.remove(&name);
+                out.push_str(&expanded);
+            }
+            _ => out.push('$'),
+        }
+    }
+
+    Ok(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn substitutes_a_single_reference() {
+        std::env::set_var("INTERPOLATE_TEST_ADDR", "127.0.0.1");
+        let mut visited = HashSet::new();
+        let out = expand("${INTERPOLATE_TEST_ADDR}:8080", &mut visited).unwrap();
+        assert_eq!(out, "127.0.0.1:8080");
+    }
+
+    #[test]
+    fn substitutes_multiple_references() {
+        std::env::set_var("INTERPOLATE_TEST_HOST", "127.0.0.1");
+        std::env::set_var("INTERPOLATE_TEST_PORT", "8000");
+        let mut visited = HashSet::new();
+        let out = expand(
+            "${INTERPOLATE_TEST_HOST}:${INTERPOLATE_TEST_PORT}",
+            &mut visited,
+        )
+        .unwrap();
+        assert_eq!(out, "127.0.0.1:8000");
+    }
+
+    #[test]
+    fn dollar_dollar_is_a_literal_dollar() {
+        let mut visited = HashSet::new();
+        let out = expand("$$5", &mut visited).unwrap();
+        assert_eq!(out, "$5");
+    }
+
+    #[test]
+    fn backslash_dollar_is_a_literal_dollar() {
+        let mut visited = HashSet::new();
+        let out = expand(r"\$5", &mut visited).unwrap();
+        assert_eq!(out, "$5");
+    }
+
+    #[test]
+    fn errors_on_missing_variable() {
+        let mut visited = HashSet::new();
+        let err = expand("${INTERPOLATE_TEST_MISSING}", &mut visited).unwrap_err();
+        assert!(matches!(err, ExpandError::NotSet(name) if name == "INTERPOLATE_TEST_MISSING"));
+    }
+
+    #[test]
+    fn errors_on_unterminated_reference() {
+        let mut visited = HashSet::new();
+        let err = expand("${OOPS", &mut visited).unwrap_err();
+        assert!(matches!(err, ExpandError::Unterminated(0)));
+    }
+
+    #[test]
+    fn errors_on_a_self_reference_cycle() {
+        std::env::set_var("INTERPOLATE_TEST_SELF", "${INTERPOLATE_TEST_SELF}");
+        let mut visited = HashSet::new();
+        let err = expand("${INTERPOLATE_TEST_SELF}", &mut visited).unwrap_err();
+        assert!(matches!(err, ExpandError::Cycle(name) if name == "INTERPOLATE_TEST_SELF"));
+    }
+
+    #[test]
+    fn errors_on_a_mutual_reference_cycle() {
+        std::env::set_var("INTERPOLATE_TEST_A", "${INTERPOLATE_TEST_B}");
+        std::env::set_var("INTERPOLATE_TEST_B", "${INTERPOLATE_TEST_A}");
+        let mut visited = HashSet::new();
+        let err = expand("${INTERPOLATE_TEST_A}", &mut visited).unwrap_err();
+        assert!(matches!(err, ExpandError::Cycle(name) if name == "INTERPOLATE_TEST_A"));
+    }
+}
@@ -2,9 +2,14 @@
 
 use std::{env::VarError, str::FromStr};
 
+pub mod auto;
+pub mod collections;
 pub mod custom;
+pub mod interpolate;
 
+pub use collections::List;
 pub use custom::Custom;
+pub use interpolate::interpolate;
 
 /// Error from reading the env var.
 #[derive(Debug, thiserror::Error)]
@@ -34,6 +39,17 @@ impl<T> Error<T> {
     }
 }
 
+/// The offending input that failed to parse: the env var value, or, for [`or_parse`], the
+/// default. Only materialized on the error path, so the happy path pays no extra allocation.
+#[derive(Debug)]
+pub struct InputString(pub String);
+
+impl std::fmt::Display for InputString {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
 /// Error while processing the value.
 #[derive(Debug, thiserror::Error)]
 pub enum ValueError<T> {
@@ -41,8 +57,14 @@ pub enum ValueError<T> {
     #[error("value is not a valid unicode")]
     NonUnicode,
     /// The value could not be parsed from a string.
-    #[error("unable to parse: {0}")]
-    Parse(#[source] T),
+    #[error("unable to parse \"{input}\": {source}")]
+    Parse {
+        /// The offending input value.
+        input: InputString,
+        /// The underlying parse error.
+        #[source]
+        source: T,
+    },
 }
 
 /// Error while processing a required variable.
@@ -63,8 +85,16 @@ pub enum OrParseError<T> {
     #[error(transparent)]
     Value(ValueError<T>),
     /// The default could not be properly parsed.
-    #[error("unable to parse the default value while the variable was not set: {0}")]
-    ParseDefault(T),
+    #[error(
+        "unable to parse the default value \"{input}\" while the variable was not set: {source}"
+    )]
+    ParseDefault {
+        /// The offending default value.
+        input: InputString,
+        /// The underlying parse error.
+        #[source]
+        source: T,
+    },
 }
 
 /// Get the value of environment variable `key` and parse it into the type `T` if variable is set.
@@ -80,10 +110,16 @@ where
         Err(VarError::NotPresent) => return Ok(None),
         Err(VarError::NotUnicode(_)) => return Err(Error::new(key, ValueError::NonUnicode)),
     };
-    let val = val
-        .parse()
-        .map_err(|err| Error::new(key, ValueError::Parse(err)))?;
-    Ok(Some(val))
+    match val.parse() {
+        Ok(parsed) => Ok(Some(parsed)),
+        Err(source) => Err(Error::new(
+            key,
+            ValueError::Parse {
+                input: InputString(val),
+                source,
+            },
+        )),
+    }
 }
 
 /// Get the value of environment variable `key` and parse it into the type `T` if variable is set.
@@ -143,9 +179,233 @@ where
     if let Some(val) = val {
         return Ok(val);
     }
-    let val = default
-        .into()
-        .parse()
-        .map_err(|err| Error::new(key, OrParseError::ParseDefault(err)))?;
-    Ok(val)
+    let default = default.into();
+    match default.parse() {
+        Ok(val) => Ok(val),
+        Err(source) => Err(Error::new(
+            key,
+            OrParseError::ParseDefault {
+                input: InputString(default),
+                source,
+            },
+        )),
+    }
+}
+
+/// Error while processing a required variable read via [`must_os`].
+#[derive(Debug, thiserror::Error)]
+pub enum MustOsError<T> {
+    /// The variable was not set.
+    #[error("not set")]
+    NotSet,
+    /// The value couldn't be processed.
+    #[error(transparent)]
+    Value(T),
+}
+
+/// Get the value of environment variable `key` and convert it into the type `T` if variable is
+/// set, without requiring the raw value to be valid unicode. If the variable is not set -
+/// returns [`None`].
+///
+/// Works directly with [`std::path::PathBuf`] and [`std::ffi::OsString`]. Any other
+/// `T: std::str::FromStr` (e.g. `u16`) needs to be wrapped in [`custom::ViaStr`], since it
+/// implements [`custom::FromOsStr`] - see its docs for an example.
+pub fn maybe_os<T>(key: &'static str) -> Result<Option<T>, Error<T::Err>>
+where
+    T: custom::FromOsStr,
+{
+    let val = match std::env::var_os(key) {
+        Some(val) => val,
+        None => return Ok(None),
+    };
+    let val = T::from_os_str(&val).map_err(|err| Error::new(key, err))?;
+    Ok(Some(val))
+}
+
+/// Get the value of environment variable `key` and convert it into the type `T`, without
+/// requiring the raw value to be valid unicode.
+///
+/// Works directly with [`std::path::PathBuf`] and [`std::ffi::OsString`]. Any other
+/// `T: std::str::FromStr` (e.g. `u16`) needs to be wrapped in [`custom::ViaStr`], since it
+/// implements [`custom::FromOsStr`] - see its docs for an example.
+pub fn must_os<T>(key: &'static str) -> Result<T, Error<MustOsError<T::Err>>>
+where
+    T: custom::FromOsStr,
+{
+    match maybe_os(key) {
+        Ok(Some(val)) => Ok(val),
+        Ok(None) => Err(Error::new(key, MustOsError::NotSet)),
+        Err(err) => Err(err.map_reason(MustOsError::Value)),
+    }
+}
+
+/// Get the value of environment variable `key` and convert it into the type `T` if variable is
+/// set, without requiring the raw value to be valid unicode. If the variable is not set -
+/// returns the `default` argument.
+///
+/// Works directly with [`std::path::PathBuf`] and [`std::ffi::OsString`]. Any other
+/// `T: std::str::FromStr` (e.g. `u16`) needs to be wrapped in [`custom::ViaStr`], since it
+/// implements [`custom::FromOsStr`] - see its docs for an example.
+pub fn or_os<T>(key: &'static str, default: T) -> Result<T, Error<T::Err>>
+where
+    T: custom::FromOsStr,
+{
+    let val = maybe_os(key)?;
+    Ok(val.unwrap_or(default))
+}
+
+/// Get the value of environment variable `key` and parse it into `T`, picking
+/// [`custom::FromStr`] over [`std::str::FromStr`] when `T` implements both, so the
+/// [`Custom`] wrapper isn't needed at the call site.
+///
+/// ```no_run
+/// let value: u8 = envfury::get!(u8, "MY_ONE_OR_TWO")?;
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+///
+/// This is a macro rather than a generic function because the autoref-specialization trick
+/// in [`auto`] (the same one `clap` uses for its `auto` value parser) only resolves the way
+/// you want when `T` is a concrete type at the call site; see [`auto::ViaCustom`] for details.
+///
+/// Unlike [`must`] and [`maybe`], the parse error isn't preserved as a `source()` you can
+/// `downcast` back to the original `T::Err` - it's stringified into [`auto::GetError`] first,
+/// since [`custom::FromStr`] and [`std::str::FromStr`] have different `Err` types and this
+/// macro needs one concrete error type regardless of which was selected.
+#[macro_export]
+macro_rules! get {
+    ($t:ty, $key:expr) => {{
+        let key: &'static str = $key;
+        match ::std::env::var(key) {
+            ::std::result::Result::Ok(val) => {
+                #[allow(unused_imports)]
+                use $crate::auto::{ViaCustom as _, ViaStd as _};
+                let tag = $crate::auto::Tag::<$t>(::std::marker::PhantomData);
+                match (&&tag).get_via(&val) {
+                    ::std::result::Result::Ok(parsed) => ::std::result::Result::Ok(parsed),
+                    ::std::result::Result::Err(source) => {
+                        ::std::result::Result::Err($crate::Error::new(
+                            key,
+                            $crate::MustError::Value($crate::ValueError::Parse {
+                                input: $crate::InputString(val),
+                                source,
+                            }),
+                        ))
+                    }
+                }
+            }
+            ::std::result::Result::Err(::std::env::VarError::NotPresent) => {
+                ::std::result::Result::Err($crate::Error::new(key, $crate::MustError::NotSet))
+            }
+            ::std::result::Result::Err(::std::env::VarError::NotUnicode(_)) => {
+                ::std::result::Result::Err($crate::Error::new(
+                    key,
+                    $crate::MustError::Value($crate::ValueError::NonUnicode),
+                ))
+            }
+        }
+    }};
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug)]
+    struct StdOnly(u8);
+
+    impl std::str::FromStr for StdOnly {
+        type Err = std::num::ParseIntError;
+
+        fn from_str(s: &str) -> Result<Self, Self::Err> {
+            s.parse().map(StdOnly)
+        }
+    }
+
+    #[derive(Debug)]
+    struct Picky(u8);
+
+    impl custom::FromStr for Picky {
+        type Err = &'static str;
+
+        fn from_str(s: &str) -> Result<Self, Self::Err> {
+            if s == "one" {
+                Ok(Picky(1))
+            } else {
+                Err("not one")
+            }
+        }
+    }
+
+    impl std::str::FromStr for Picky {
+        type Err = std::num::ParseIntError;
+
+        fn from_str(s: &str) -> Result<Self, Self::Err> {
+            s.parse().map(Picky)
+        }
+    }
+
+    #[test]
+    fn get_falls_back_to_std_from_str_when_no_custom_impl_exists() {
+        std::env::set_var("GET_TEST_STD_ONLY", "7");
+        let StdOnly(val) = get!(StdOnly, "GET_TEST_STD_ONLY").unwrap();
+        assert_eq!(val, 7);
+    }
+
+    #[test]
+    fn get_prefers_custom_from_str_over_std_from_str() {
+        // `Picky`'s `custom::FromStr` only accepts "one"; its `std::str::FromStr` only accepts
+        // digits. If `get!` fell back to `std::str::FromStr`, this would fail to parse.
+        std::env::set_var("GET_TEST_PICKY", "one");
+        let Picky(val) = get!(Picky, "GET_TEST_PICKY").unwrap();
+        assert_eq!(val, 1);
+    }
+
+    #[test]
+    fn get_errors_when_the_variable_is_not_set() {
+        std::env::remove_var("GET_TEST_NOT_SET");
+        let err = get!(StdOnly, "GET_TEST_NOT_SET").unwrap_err();
+        assert!(matches!(err.reason, MustError::NotSet));
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn get_errors_on_non_unicode_values() {
+        use std::os::unix::ffi::OsStrExt;
+
+        let bytes = [0x66, 0x6f, 0x80, 0x6f];
+        let value = std::ffi::OsStr::from_bytes(&bytes);
+        std::env::set_var("GET_TEST_NON_UNICODE", value);
+        let err = get!(StdOnly, "GET_TEST_NON_UNICODE").unwrap_err();
+        assert!(matches!(
+            err.reason,
+            MustError::Value(ValueError::NonUnicode)
+        ));
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn must_os_round_trips_non_unicode_bytes() {
+        use std::os::unix::ffi::OsStrExt;
+
+        let bytes = [0x66, 0x6f, 0x80, 0x6f];
+        let value = std::ffi::OsStr::from_bytes(&bytes);
+        std::env::set_var("MUST_OS_TEST_NON_UNICODE", value);
+
+        let path = must_os::<std::path::PathBuf>("MUST_OS_TEST_NON_UNICODE").unwrap();
+        assert_eq!(path.as_os_str().as_bytes(), &bytes);
+    }
+
+    #[test]
+    fn must_error_display_surfaces_the_offending_value() {
+        std::env::set_var("MUST_TEST_BAD_VALUE", "oops");
+        let err = must::<u16>("MUST_TEST_BAD_VALUE").unwrap_err();
+        assert!(err.to_string().contains("\"oops\""));
+    }
+
+    #[test]
+    fn or_parse_error_display_surfaces_the_offending_default() {
+        std::env::remove_var("OR_PARSE_TEST_NOT_SET");
+        let err = or_parse::<u16>("OR_PARSE_TEST_NOT_SET", "oops").unwrap_err();
+        assert!(err.to_string().contains("\"oops\""));
+    }
 }